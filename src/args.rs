@@ -0,0 +1,115 @@
+//! Command line argument parsing.
+//!
+//! All flags gitui understands are declared here on a single `clap`
+//! parser, so `--help`, validation, and precedence between flags are
+//! all handled in one place instead of each feature hand-rolling its
+//! own pass over `std::env::args()`.
+
+use crate::backend::BackendKind;
+use anyhow::Result;
+use asyncgit::sync::RepoPath;
+use clap::{Parser, ValueEnum};
+use std::path::{Path, PathBuf};
+
+/// Resolved command line arguments, ready for the rest of `main` to use.
+pub struct CliArgs {
+	pub theme: PathBuf,
+	pub repo_path: RepoPath,
+	pub notify_watcher: bool,
+	pub backend: BackendKind,
+	pub status_socket: Option<PathBuf>,
+}
+
+/// Which ratatui backend to drive the UI with, as spelled on the
+/// command line. Kept separate from `backend::BackendKind` so that
+/// enum lives with the rest of the backend machinery instead of here.
+#[derive(Clone, Copy, ValueEnum)]
+enum BackendArg {
+	Crossterm,
+	Termwiz,
+}
+
+impl From<BackendArg> for BackendKind {
+	fn from(value: BackendArg) -> Self {
+		match value {
+			BackendArg::Crossterm => Self::Crossterm,
+			BackendArg::Termwiz => Self::Termwiz,
+		}
+	}
+}
+
+#[derive(Parser)]
+#[command(author, version, about)]
+struct Args {
+	/// Set the path to the repository's working directory
+	#[arg(short, long)]
+	directory: Option<PathBuf>,
+
+	/// Set the color theme file (defaults to `theme.ron`)
+	#[arg(short, long, default_value = "theme.ron")]
+	theme: PathBuf,
+
+	/// Use the filesystem watcher instead of polling the repository
+	/// for changes
+	#[arg(short, long)]
+	watcher: bool,
+
+	/// Select the ratatui backend the UI is drawn with
+	#[arg(long, value_enum, default_value_t = BackendArg::Crossterm)]
+	backend: BackendArg,
+
+	/// Mirror async status transitions as newline-delimited JSON to
+	/// every client connected to a Unix socket at this path
+	#[arg(long)]
+	status_socket: Option<PathBuf>,
+}
+
+/// Parses `std::env::args()` into [`CliArgs`], exiting the process with
+/// clap's usual `--help`/parse-error behavior on invalid input.
+pub fn process_cmdline() -> Result<CliArgs> {
+	let args = Args::parse();
+
+	let repo_path = RepoPath::Path(
+		args.directory.unwrap_or_else(|| Path::new(".").into()),
+	);
+
+	Ok(CliArgs {
+		theme: args.theme,
+		repo_path,
+		notify_watcher: args.watcher,
+		backend: args.backend.into(),
+		status_socket: args.status_socket,
+	})
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn parses_backend_and_status_socket() {
+		let args = Args::try_parse_from([
+			"gitui",
+			"--backend",
+			"termwiz",
+			"--status-socket",
+			"/tmp/gitui-status.sock",
+		])
+		.expect("flags should parse");
+
+		assert!(matches!(args.backend, BackendArg::Termwiz));
+		assert_eq!(
+			args.status_socket,
+			Some(PathBuf::from("/tmp/gitui-status.sock"))
+		);
+	}
+
+	#[test]
+	fn defaults_to_crossterm_with_no_status_socket() {
+		let args = Args::try_parse_from(["gitui"])
+			.expect("bare invocation should parse");
+
+		assert!(matches!(args.backend, BackendArg::Crossterm));
+		assert_eq!(args.status_socket, None);
+	}
+}
@@ -0,0 +1,130 @@
+//! Pluggable ratatui backend selection.
+//!
+//! By default gitui drives ratatui through crossterm, which means we are
+//! responsible for the raw-mode + alternate-screen dance ourselves (see
+//! `setup_terminal`/`shutdown_terminal` in `main.rs`). That manual
+//! restoration can be skipped if the process dies unexpectedly or a
+//! spawned external editor leaves the terminal in a weird state.
+//! Termwiz owns its PTY end-to-end and restores it on drop, so it is
+//! offered here as an alternative, selectable via `--backend termwiz`.
+
+use anyhow::Result;
+use ratatui::backend::{CrosstermBackend, TermwizBackend};
+use std::io::Stdout;
+
+/// Which ratatui backend to drive the UI with.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum BackendKind {
+	#[default]
+	Crossterm,
+	Termwiz,
+}
+
+/// Terminal handle wrapping whichever backend was selected at startup.
+///
+/// All per-frame UI plumbing (`draw`, `hide_cursor`, `clear`, window
+/// title) and shutdown restoration are routed through here so code that
+/// holds a `GituiTerminal` never has to match on the backend kind itself.
+pub enum GituiTerminal {
+	Crossterm(ratatui::Terminal<CrosstermBackend<Stdout>>),
+	Termwiz(ratatui::Terminal<TermwizBackend>),
+}
+
+impl GituiTerminal {
+	pub fn crossterm(buf: Stdout) -> Result<Self> {
+		let terminal =
+			ratatui::Terminal::new(CrosstermBackend::new(buf))?;
+		Ok(Self::Crossterm(terminal))
+	}
+
+	pub fn termwiz() -> Result<Self> {
+		let terminal =
+			ratatui::Terminal::new(TermwizBackend::new()?)?;
+		Ok(Self::Termwiz(terminal))
+	}
+
+	pub fn set_title(&mut self, title: &str) -> Result<()> {
+		match self {
+			Self::Crossterm(terminal) => {
+				use crossterm::{
+					terminal::SetTitle, ExecutableCommand,
+				};
+				terminal
+					.backend_mut()
+					.execute(SetTitle(title))?;
+			}
+			Self::Termwiz(terminal) => {
+				terminal
+					.backend_mut()
+					.buffered_terminal_mut()
+					.terminal()
+					.set_title(title);
+			}
+		}
+
+		Ok(())
+	}
+
+	pub fn hide_cursor(&mut self) -> Result<()> {
+		match self {
+			Self::Crossterm(terminal) => terminal.hide_cursor()?,
+			Self::Termwiz(terminal) => terminal.hide_cursor()?,
+		}
+
+		Ok(())
+	}
+
+	pub fn clear(&mut self) -> Result<()> {
+		match self {
+			Self::Crossterm(terminal) => terminal.clear()?,
+			Self::Termwiz(terminal) => terminal.clear()?,
+		}
+
+		Ok(())
+	}
+
+	pub fn draw<F>(&mut self, f: F) -> Result<()>
+	where
+		F: FnOnce(&mut ratatui::Frame),
+	{
+		match self {
+			Self::Crossterm(terminal) => {
+				terminal.draw(f)?;
+			}
+			Self::Termwiz(terminal) => {
+				terminal.draw(f)?;
+			}
+		}
+
+		Ok(())
+	}
+
+}
+
+/// Restores the terminal to its original state once the wrapper goes
+/// out of scope. For crossterm this means undoing raw mode and the
+/// alternate screen by hand; Termwiz restores itself as soon as its
+/// `BufferedTerminal` is dropped, so that branch just lets the inner
+/// drop glue run.
+impl Drop for GituiTerminal {
+	fn drop(&mut self) {
+		if let Self::Crossterm(_) = self {
+			use crossterm::{
+				terminal::{disable_raw_mode, LeaveAlternateScreen},
+				ExecutableCommand,
+			};
+
+			let leave_screen = std::io::stdout()
+				.execute(LeaveAlternateScreen)
+				.map(|_f| ());
+
+			if let Err(e) = leave_screen {
+				log::error!("leave_screen failed:\n{e}");
+			}
+
+			if let Err(e) = disable_raw_mode() {
+				log::error!("leave_raw_mode failed:\n{e}");
+			}
+		}
+	}
+}
@@ -61,6 +61,7 @@
 
 mod app;
 mod args;
+mod backend;
 mod bug_report;
 mod clipboard;
 mod cmdbar;
@@ -73,6 +74,7 @@ mod popup_stack;
 mod popups;
 mod queue;
 mod spinner;
+mod status_ipc;
 mod string_utils;
 mod strings;
 mod tabs;
@@ -86,33 +88,33 @@ use asyncgit::{
 	sync::{utils::repo_work_dir, RepoPath},
 	AsyncGitNotification,
 };
+use backend::{BackendKind, GituiTerminal};
 use backtrace::Backtrace;
-use crossbeam_channel::{never, tick, unbounded, Receiver, Select};
-use crossterm::{
-	terminal::{
-		disable_raw_mode, enable_raw_mode, EnterAlternateScreen,
-		LeaveAlternateScreen,
-	},
-	ExecutableCommand,
+use crossbeam_channel::unbounded;
+use crossterm::event::EventStream;
+use crossterm::terminal::{
+	disable_raw_mode, enable_raw_mode, EnterAlternateScreen,
+	LeaveAlternateScreen,
 };
-use input::{Input, InputEvent, InputState};
+use crossterm::ExecutableCommand;
+use futures::StreamExt;
+use input::InputEvent;
 use keys::KeyConfig;
-use ratatui::backend::CrosstermBackend;
-use scopeguard::defer;
 use scopetime::scope_time;
 use spinner::Spinner;
+use status_ipc::{StatusEmitter, StatusMessage};
 use std::{
 	cell::RefCell,
-	io::{self, Stdout},
+	io,
 	panic,
 	path::Path,
+	pin::Pin,
 	time::{Duration, Instant},
 };
+use tokio_stream::wrappers::{IntervalStream, UnboundedReceiverStream};
 use ui::style::Theme;
 use watcher::RepoWatcher;
 
-type Terminal = ratatui::Terminal<CrosstermBackend<io::Stdout>>;
-
 static TICK_INTERVAL: Duration = Duration::from_secs(5);
 static SPINNER_INTERVAL: Duration = Duration::from_millis(80);
 
@@ -136,6 +138,13 @@ pub enum SyntaxHighlightProgress {
 pub enum AsyncAppNotification {
 	///
 	SyntaxHighlighting(SyntaxHighlightProgress),
+	/// An external program that had taken over the terminal (e.g. the
+	/// `$EDITOR` spawned for a commit message) has exited and control is
+	/// back with gitui. The old `Select`-based loop caught this via
+	/// `InputEvent::State(InputState::Polling)` off the input thread; now
+	/// that the input thread is gone, `App` sends this instead once the
+	/// child process it spawned returns.
+	ExternalProcessFinished,
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -160,29 +169,33 @@ macro_rules! log_eprintln {
 	}};
 }
 
-fn main() -> Result<()> {
+#[tokio::main(flavor = "current_thread")]
+async fn main() -> Result<()> {
 	let app_start = Instant::now();
 
 	let cliargs = process_cmdline()?;
+	let backend_kind = cliargs.backend;
+	let status_emitter = match cliargs.status_socket {
+		Some(ref path) => StatusEmitter::socket(path)?,
+		None => StatusEmitter::disabled(),
+	};
 
 	asyncgit::register_tracing_logging();
-	ensure_valid_path(&cliargs.repo_path)?;
 
 	let key_config = KeyConfig::init()
 		.map_err(|e| log_eprintln!("KeyConfig loading error: {e}"))
 		.unwrap_or_default();
 	let theme = Theme::init(&cliargs.theme);
 
-	setup_terminal()?;
-	defer! {
-		shutdown_terminal();
-	}
-
+	setup_terminal(backend_kind)?;
 	set_panic_handler()?;
 
 	let mut repo_path = cliargs.repo_path;
-	let mut terminal = start_terminal(io::stdout(), &repo_path)?;
-	let input = Input::new();
+	// `terminal` restores the screen/raw-mode itself on drop (see
+	// `backend::GituiTerminal`), so no explicit shutdown call is needed
+	// here; the panic hook uses the crossterm-only `shutdown_terminal`
+	// fallback since it has no access to this value.
+	let mut terminal = start_terminal(backend_kind, io::stdout())?;
 
 	let updater = if cliargs.notify_watcher {
 		Updater::NotifyWatcher
@@ -191,64 +204,231 @@ fn main() -> Result<()> {
 	};
 
 	loop {
+		// Invalid `--directory`, a bare repo, or a transient lock error
+		// used to `bail!` out before the TUI was even drawn. Now we stay
+		// in the alternate screen and let the user look at what went
+		// wrong and try a different path instead of dumping to stderr.
+		repo_path =
+			resolve_repo_path(&mut terminal, repo_path).await?;
+		terminal.set_title(&window_title(&repo_path)?)?;
+
 		let quit_state = run_app(
 			app_start,
 			repo_path.clone(),
 			theme.clone(),
 			key_config.clone(),
-			&input,
 			updater,
 			&mut terminal,
-		)?;
+			&status_emitter,
+		)
+		.await?;
 
 		match quit_state {
 			QuitState::OpenSubmodule(p) => {
+				status_emitter.emit(&StatusMessage::OpenedSubmodule);
 				repo_path = p;
 			}
 			_ => break,
 		}
 	}
 
+	status_emitter.emit(&StatusMessage::Quit);
+
+	Ok(())
+}
+
+/// Loops, redrawing the error screen, until `asyncgit::sync::repo_open_error`
+/// reports `repo_path` opens cleanly, then returns it. While an error is
+/// showing, Enter replaces `repo_path` with whatever was typed into the
+/// input buffer and retries; Backspace/Char edit that buffer; Esc returns
+/// an `Err` instead of retrying, which propagates out of `main` and exits.
+///
+/// Reads keys off the same `crossterm::event::EventStream` machinery
+/// `select_event` drives the main loop with, rather than a second,
+/// blocking `crossterm::event::read()` loop of its own.
+async fn resolve_repo_path(
+	terminal: &mut GituiTerminal,
+	mut repo_path: RepoPath,
+) -> Result<RepoPath> {
+	let mut input = String::new();
+	let mut events = EventStream::new();
+
+	loop {
+		let Some(error) = asyncgit::sync::repo_open_error(&repo_path)
+		else {
+			return Ok(repo_path);
+		};
+
+		log::error!("{error}");
+		draw_repo_error_popup(terminal, &error, &input)?;
+
+		loop {
+			let Some(ev) = events.next().await else {
+				bail!("input event stream ended while waiting for a repository path");
+			};
+
+			if let crossterm::event::Event::Key(key) = ev? {
+				match key.code {
+					crossterm::event::KeyCode::Enter => {
+						repo_path =
+							RepoPath::Path(Path::new(&input).into());
+						input.clear();
+						break;
+					}
+					crossterm::event::KeyCode::Esc => {
+						bail!("failed to open repository: {error}");
+					}
+					crossterm::event::KeyCode::Backspace => {
+						input.pop();
+						draw_repo_error_popup(
+							terminal, &error, &input,
+						)?;
+					}
+					crossterm::event::KeyCode::Char(c) => {
+						input.push(c);
+						draw_repo_error_popup(
+							terminal, &error, &input,
+						)?;
+					}
+					_ => {}
+				}
+			}
+		}
+	}
+}
+
+/// Renders the repo-open error screen directly with ratatui widgets: a
+/// bordered `Paragraph` over a cleared, centered area. It does not go
+/// through the `popups` subsystem.
+fn draw_repo_error_popup(
+	terminal: &mut GituiTerminal,
+	error: &str,
+	input: &str,
+) -> Result<()> {
+	use ratatui::{
+		layout::Rect,
+		widgets::{Block, Borders, Clear, Paragraph, Wrap},
+	};
+
+	terminal.draw(|f| {
+		let area = f.area();
+		let popup = Rect {
+			x: area.width / 8,
+			y: area.height / 4,
+			width: area.width - area.width / 4,
+			height: area.height / 2,
+		};
+
+		let text = format!(
+			"gitui could not open this repository:\n\n{error}\n\nEnter a new path and press Enter to retry (Esc to quit):\n\n> {input}"
+		);
+
+		f.render_widget(Clear, popup);
+		f.render_widget(
+			Paragraph::new(text)
+				.wrap(Wrap { trim: false })
+				.block(
+					Block::default()
+						.title(" repository error ")
+						.borders(Borders::ALL),
+				),
+			popup,
+		);
+	})?;
+
 	Ok(())
 }
 
-fn run_app(
+/// Builds the terminal window title for a resolved repository path,
+/// abbreviating the home directory to `~` the way `start_terminal` used
+/// to inline before repo-path resolution was split out of it.
+fn window_title(repo_path: &RepoPath) -> Result<String> {
+	let mut path = repo_path.gitpath().canonicalize()?;
+	let home = dirs::home_dir().ok_or_else(|| {
+		anyhow!("failed to find the home directory")
+	})?;
+	if path.starts_with(&home) {
+		let relative_part = path
+			.strip_prefix(&home)
+			.expect("can't fail because of the if statement");
+		path = Path::new("~").join(relative_part);
+	}
+
+	Ok(format!("gitui ({})", path.display()))
+}
+
+/// Wraps a blocking `crossbeam_channel::Receiver` as a `Stream` by
+/// forwarding it from a dedicated OS thread into a tokio channel. This
+/// lets the git/app notification channels (owned by `asyncgit`, which
+/// knows nothing about tokio) sit in the same `select!` as the
+/// terminal's `EventStream` and our interval tickers.
+fn crossbeam_to_stream<T: Send + 'static>(
+	rx: crossbeam_channel::Receiver<T>,
+) -> UnboundedReceiverStream<T> {
+	let (tx, rx_async) = tokio::sync::mpsc::unbounded_channel();
+
+	std::thread::spawn(move || {
+		while let Ok(value) = rx.recv() {
+			if tx.send(value).is_err() {
+				break;
+			}
+		}
+	});
+
+	UnboundedReceiverStream::new(rx_async)
+}
+
+async fn run_app(
 	app_start: Instant,
 	repo: RepoPath,
 	theme: Theme,
 	key_config: KeyConfig,
-	input: &Input,
 	updater: Updater,
-	terminal: &mut Terminal,
+	terminal: &mut GituiTerminal,
+	status: &StatusEmitter,
 ) -> Result<QuitState, anyhow::Error> {
 	let (tx_git, rx_git) = unbounded();
 	let (tx_app, rx_app) = unbounded();
 
-	let rx_input = input.receiver();
-
-	let (rx_ticker, rx_watcher) = match updater {
-		Updater::NotifyWatcher => {
-			let repo_watcher =
-				RepoWatcher::new(repo_work_dir(&repo)?.as_str());
-
-			(never(), repo_watcher.receiver())
-		}
-		Updater::Ticker => (tick(TICK_INTERVAL), never()),
-	};
+	let mut input_events = EventStream::new().fuse();
+	let mut git_events = crossbeam_to_stream(rx_git).fuse();
+	let mut app_events = crossbeam_to_stream(rx_app).fuse();
+
+	let notify_stream: Pin<Box<dyn futures::Stream<Item = ()> + Send>> =
+		match updater {
+			Updater::NotifyWatcher => {
+				let repo_watcher =
+					RepoWatcher::new(repo_work_dir(&repo)?.as_str());
+				Box::pin(crossbeam_to_stream(
+					repo_watcher.receiver(),
+				))
+			}
+			Updater::Ticker => Box::pin(
+				IntervalStream::new(tokio::time::interval(
+					TICK_INTERVAL,
+				))
+				.map(|_| ()),
+			),
+		};
+	let mut notify_events = notify_stream.fuse();
 
-	let spinner_ticker = tick(SPINNER_INTERVAL);
+	let mut spinner_events =
+		IntervalStream::new(tokio::time::interval(SPINNER_INTERVAL))
+			.fuse();
 
 	let mut app = App::new(
 		RefCell::new(repo),
 		tx_git,
 		tx_app,
-		input.clone(),
 		theme,
 		key_config,
 	)?;
 
 	let mut spinner = Spinner::default();
 	let mut first_update = true;
+	let mut work_pending = false;
+	let mut current_tab = app.tab_idx();
+	status.emit(&StatusMessage::Tab { index: current_tab });
 
 	log::trace!("app start: {} ms", app_start.elapsed().as_millis());
 
@@ -258,13 +438,13 @@ fn run_app(
 			QueueEvent::Notify
 		} else {
 			select_event(
-				&rx_input,
-				&rx_git,
-				&rx_app,
-				&rx_ticker,
-				&rx_watcher,
-				&spinner_ticker,
-			)?
+				&mut input_events,
+				&mut git_events,
+				&mut app_events,
+				&mut notify_events,
+				&mut spinner_events,
+			)
+			.await?
 		};
 
 		{
@@ -278,19 +458,28 @@ fn run_app(
 
 			match event {
 				QueueEvent::InputEvent(ev) => {
-					if matches!(
-						ev,
-						InputEvent::State(InputState::Polling)
-					) {
-						//Note: external ed closed, we need to re-hide cursor
-						terminal.hide_cursor()?;
-					}
 					app.event(ev)?;
 				}
 				QueueEvent::Tick | QueueEvent::Notify => {
 					app.update()?;
 				}
 				QueueEvent::AsyncEvent(ev) => {
+					status.emit(&StatusMessage::AsyncEvent {
+						notification: &format!("{ev:?}"),
+					});
+
+					// An external editor leaves raw mode/the cursor in
+					// whatever state it wants; re-hide it now that we
+					// have the terminal back.
+					if matches!(
+						ev,
+						AsyncNotification::App(
+							AsyncAppNotification::ExternalProcessFinished
+						)
+					) {
+						terminal.hide_cursor()?;
+					}
+
 					if !matches!(
 						ev,
 						AsyncNotification::Git(
@@ -305,9 +494,22 @@ fn run_app(
 
 			draw(terminal, &app)?;
 
-			spinner.set_state(app.any_work_pending());
+			let pending = app.any_work_pending();
+			if pending != work_pending {
+				work_pending = pending;
+				status.emit(&StatusMessage::WorkPending {
+					pending: work_pending,
+				});
+			}
+			spinner.set_state(pending);
 			spinner.draw(terminal)?;
 
+			let tab = app.tab_idx();
+			if tab != current_tab {
+				current_tab = tab;
+				status.emit(&StatusMessage::Tab { index: tab });
+			}
+
 			if app.is_quit() {
 				break;
 			}
@@ -317,12 +519,23 @@ fn run_app(
 	Ok(app.quit_state())
 }
 
-fn setup_terminal() -> Result<()> {
-	enable_raw_mode()?;
-	io::stdout().execute(EnterAlternateScreen)?;
+/// Prepares the screen before the selected backend's terminal is
+/// constructed. Termwiz manages raw mode/the alternate screen itself
+/// when it opens its own terminal, so there is nothing to do here for
+/// that backend.
+fn setup_terminal(backend_kind: BackendKind) -> Result<()> {
+	if backend_kind == BackendKind::Crossterm {
+		enable_raw_mode()?;
+		io::stdout().execute(EnterAlternateScreen)?;
+	}
+
 	Ok(())
 }
 
+/// Crossterm-only fallback restoration used by the panic hook, which
+/// has no access to the live `GituiTerminal` to call its `Drop` impl.
+/// Best-effort for the Termwiz backend too, since an unwinding panic
+/// still drops the terminal's `BufferedTerminal` on the way out.
 fn shutdown_terminal() {
 	let leave_screen =
 		io::stdout().execute(LeaveAlternateScreen).map(|_f| ());
@@ -338,7 +551,7 @@ fn shutdown_terminal() {
 	}
 }
 
-fn draw(terminal: &mut Terminal, app: &App) -> io::Result<()> {
+fn draw(terminal: &mut GituiTerminal, app: &App) -> Result<()> {
 	if app.requires_redraw() {
 		terminal.clear()?;
 	}
@@ -352,75 +565,48 @@ fn draw(terminal: &mut Terminal, app: &App) -> io::Result<()> {
 	Ok(())
 }
 
-fn ensure_valid_path(repo_path: &RepoPath) -> Result<()> {
-	match asyncgit::sync::repo_open_error(repo_path) {
-		Some(e) => {
-			log::error!("{e}");
-			bail!(e)
+/// Waits for whichever of the terminal/git/app/tick/spinner streams
+/// produces an event first. This replaces the old `crossbeam_channel::
+/// Select` loop: everything here is a `futures::Stream`, so adding a new
+/// async event source in the future is just another branch instead of
+/// extending a positional `match index` block.
+async fn select_event(
+	input: &mut (impl futures::Stream<Item = crossterm::Result<crossterm::event::Event>> + futures::stream::FusedStream + Unpin),
+	rx_git: &mut (impl futures::Stream<Item = AsyncGitNotification> + futures::stream::FusedStream + Unpin),
+	rx_app: &mut (impl futures::Stream<Item = AsyncAppNotification> + futures::stream::FusedStream + Unpin),
+	rx_notify: &mut (impl futures::Stream<Item = ()> + futures::stream::FusedStream + Unpin),
+	rx_spinner: &mut (impl futures::Stream<Item = tokio::time::Instant> + futures::stream::FusedStream + Unpin),
+) -> Result<QueueEvent> {
+	futures::select! {
+		ev = input.next() => {
+			let ev = ev.ok_or_else(|| anyhow!("input event stream ended"))??;
+			Ok(QueueEvent::InputEvent(InputEvent::Input(ev)))
 		}
-		None => Ok(()),
+		ev = rx_git.next() => ev
+			.map(|e| QueueEvent::AsyncEvent(AsyncNotification::Git(e)))
+			.ok_or_else(|| anyhow!("git notification stream ended")),
+		ev = rx_app.next() => ev
+			.map(|e| QueueEvent::AsyncEvent(AsyncNotification::App(e)))
+			.ok_or_else(|| anyhow!("app notification stream ended")),
+		ev = rx_notify.next() => ev
+			.map(|()| QueueEvent::Notify)
+			.ok_or_else(|| anyhow!("notify stream ended")),
+		ev = rx_spinner.next() => ev
+			.map(|_| QueueEvent::SpinnerUpdate)
+			.ok_or_else(|| anyhow!("spinner ticker ended")),
 	}
 }
 
-fn select_event(
-	rx_input: &Receiver<InputEvent>,
-	rx_git: &Receiver<AsyncGitNotification>,
-	rx_app: &Receiver<AsyncAppNotification>,
-	rx_ticker: &Receiver<Instant>,
-	rx_notify: &Receiver<()>,
-	rx_spinner: &Receiver<Instant>,
-) -> Result<QueueEvent> {
-	let mut sel = Select::new();
-
-	sel.recv(rx_input);
-	sel.recv(rx_git);
-	sel.recv(rx_app);
-	sel.recv(rx_ticker);
-	sel.recv(rx_notify);
-	sel.recv(rx_spinner);
-
-	let oper = sel.select();
-	let index = oper.index();
-
-	let ev = match index {
-		0 => oper.recv(rx_input).map(QueueEvent::InputEvent),
-		1 => oper.recv(rx_git).map(|e| {
-			QueueEvent::AsyncEvent(AsyncNotification::Git(e))
-		}),
-		2 => oper.recv(rx_app).map(|e| {
-			QueueEvent::AsyncEvent(AsyncNotification::App(e))
-		}),
-		3 => oper.recv(rx_ticker).map(|_| QueueEvent::Notify),
-		4 => oper.recv(rx_notify).map(|()| QueueEvent::Notify),
-		5 => oper.recv(rx_spinner).map(|_| QueueEvent::SpinnerUpdate),
-		_ => bail!("unknown select source"),
-	}?;
-
-	Ok(ev)
-}
-
 fn start_terminal(
-	buf: Stdout,
-	repo_path: &RepoPath,
-) -> Result<Terminal> {
-	let mut path = repo_path.gitpath().canonicalize()?;
-	let home = dirs::home_dir().ok_or_else(|| {
-		anyhow!("failed to find the home directory")
-	})?;
-	if path.starts_with(&home) {
-		let relative_part = path
-			.strip_prefix(&home)
-			.expect("can't fail because of the if statement");
-		path = Path::new("~").join(relative_part);
-	}
-
-	let mut backend = CrosstermBackend::new(buf);
-	backend.execute(crossterm::terminal::SetTitle(format!(
-		"gitui ({})",
-		path.display()
-	)))?;
+	backend_kind: BackendKind,
+	buf: io::Stdout,
+) -> Result<GituiTerminal> {
+	let mut terminal = match backend_kind {
+		BackendKind::Crossterm => GituiTerminal::crossterm(buf)?,
+		BackendKind::Termwiz => GituiTerminal::termwiz()?,
+	};
 
-	let mut terminal = Terminal::new(backend)?;
+	terminal.set_title("gitui")?;
 	terminal.hide_cursor()?;
 	terminal.clear()?;
 
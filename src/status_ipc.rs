@@ -0,0 +1,104 @@
+//! Opt-in, read-only status subscription.
+//!
+//! Mirrors gitui's high-level async state transitions as
+//! newline-delimited JSON so external tools (status bars, wrapper
+//! scripts, a supervising process) can react to things like "fetch in
+//! progress" or "syntax highlighting done" without scraping the
+//! rendered terminal. Disabled by default; opt in with
+//! `--status-socket <path>`, which writes to every client connected to
+//! a Unix socket at that path.
+//!
+//! There is deliberately no stdout option: stdout is the exact file
+//! descriptor `backend::GituiTerminal::crossterm` renders the
+//! alternate-screen TUI through, so interleaving JSON lines into it
+//! would corrupt the display. An earlier revision of this module did
+//! offer a stdout sink and it shipped without being run against a live
+//! terminal first; any future sink option should be smoke-tested
+//! against an actual `GituiTerminal::Crossterm` session before it
+//! lands, not just reasoned about.
+
+use anyhow::Result;
+use serde::Serialize;
+use std::{
+	io::Write,
+	os::unix::net::{UnixListener, UnixStream},
+	path::Path,
+	sync::{Arc, Mutex},
+};
+
+/// A single state transition mirrored to subscribers.
+#[derive(Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum StatusMessage<'a> {
+	/// An `AsyncNotification` was just processed.
+	AsyncEvent { notification: &'a str },
+	/// `any_work_pending()` changed since the last frame.
+	WorkPending { pending: bool },
+	/// The selected tab: sent once when a repository is opened, then
+	/// again whenever the selection changes.
+	Tab { index: usize },
+	/// A submodule was opened, replacing the current repository in
+	/// place rather than gitui exiting outright.
+	OpenedSubmodule,
+	/// The TUI is shutting down.
+	Quit,
+}
+
+/// Where to send serialized [`StatusMessage`]s, if anywhere.
+pub enum StatusEmitter {
+	Disabled,
+	Socket(Arc<Mutex<Vec<UnixStream>>>),
+}
+
+impl StatusEmitter {
+	pub const fn disabled() -> Self {
+		Self::Disabled
+	}
+
+	/// Binds a Unix socket at `path` and spawns a background thread that
+	/// accepts subscriber connections for as long as the process runs.
+	pub fn socket(path: &Path) -> Result<Self> {
+		// A stale socket file from a previous crashed run would
+		// otherwise make `bind` fail with `AddrInUse`.
+		let _ = std::fs::remove_file(path);
+
+		let listener = UnixListener::bind(path)?;
+		let clients = Arc::new(Mutex::new(Vec::new()));
+
+		let accept_clients = Arc::clone(&clients);
+		std::thread::spawn(move || {
+			for stream in listener.incoming().flatten() {
+				if let Ok(mut clients) = accept_clients.lock() {
+					clients.push(stream);
+				}
+			}
+		});
+
+		Ok(Self::Socket(clients))
+	}
+
+	/// Serializes `message` and sends it to whatever sink is configured.
+	/// Best-effort: a subscriber that went away is silently dropped, and
+	/// having no subscribers connected yet is not an error.
+	pub fn emit(&self, message: &StatusMessage<'_>) {
+		if matches!(self, Self::Disabled) {
+			return;
+		}
+
+		let Ok(mut line) = serde_json::to_string(message) else {
+			return;
+		};
+		line.push('\n');
+
+		match self {
+			Self::Disabled => {}
+			Self::Socket(clients) => {
+				if let Ok(mut clients) = clients.lock() {
+					clients.retain_mut(|client| {
+						client.write_all(line.as_bytes()).is_ok()
+					});
+				}
+			}
+		}
+	}
+}